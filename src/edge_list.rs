@@ -0,0 +1,273 @@
+//! Parses a trust graph from a plain-text edge-list format, so it can be run
+//! on external datasets without recompiling, instead of the inline literals
+//! `main` and `run_job` used to build graphs from:
+//!
+//! ```text
+//! A B 0.6 +
+//! B C 0.4 +
+//! pretrust A 0.7
+//! pretrust B 0.3
+//! ```
+//!
+//! Each edge line is `<source> <target> <weight> <+|->`, where the trailing
+//! sign selects a positive or negative trust edge. `pretrust <node>
+//! <weight>` lines seed the pre-trust vector. Blank lines and `#` comments
+//! are ignored. Parsing is built on `nom` so a malformed line reports a
+//! precise line number instead of failing silently or mid-file.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, multispace1},
+    combinator::map,
+    number::complete::float,
+    sequence::tuple,
+    IResult,
+};
+
+use crate::transitive_trust::Graph;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Sign {
+    Positive,
+    Negative,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Line {
+    Edge {
+        source: String,
+        target: String,
+        weight: f32,
+        sign: Sign,
+    },
+    PreTrust {
+        node: String,
+        weight: f32,
+    },
+}
+
+/// A malformed edge-list line, reported with its 1-indexed line number so a
+/// user can find it without re-reading the whole file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn identifier(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')(input)
+}
+
+fn sign(input: &str) -> IResult<&str, Sign> {
+    alt((
+        map(char('+'), |_| Sign::Positive),
+        map(char('-'), |_| Sign::Negative),
+    ))(input)
+}
+
+fn edge_line(input: &str) -> IResult<&str, Line> {
+    map(
+        tuple((
+            identifier,
+            multispace1,
+            identifier,
+            multispace1,
+            float,
+            multispace1,
+            sign,
+        )),
+        |(source, _, target, _, weight, _, sign)| Line::Edge {
+            source: source.to_string(),
+            target: target.to_string(),
+            weight,
+            sign,
+        },
+    )(input)
+}
+
+fn pretrust_line(input: &str) -> IResult<&str, Line> {
+    map(
+        tuple((tag("pretrust"), multispace1, identifier, multispace1, float)),
+        |(_, _, node, _, weight)| Line::PreTrust {
+            node: node.to_string(),
+            weight,
+        },
+    )(input)
+}
+
+fn line(input: &str) -> IResult<&str, Line> {
+    alt((pretrust_line, edge_line))(input)
+}
+
+/// Parses `input` into a `Graph` and its pre-trust seed map. Validates, as
+/// `validate_lt` used to, that self-edges carry zero weight and that all
+/// weights are non-negative.
+pub fn parse_graph(input: &str) -> Result<(Graph, HashMap<String, f32>), ParseError> {
+    let mut graph = Graph::new();
+    let mut pre_trust = HashMap::new();
+
+    for (index, raw_line) in input.lines().enumerate() {
+        let trimmed = raw_line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let line_number = index + 1;
+        let error_at = |message: String| ParseError {
+            line: line_number,
+            message,
+        };
+
+        let (remainder, parsed) =
+            line(trimmed).map_err(|err| error_at(format!("malformed edge-list line: {err:?}")))?;
+
+        if !remainder.trim().is_empty() {
+            return Err(error_at(format!(
+                "unexpected trailing input: {remainder:?}"
+            )));
+        }
+
+        match parsed {
+            Line::Edge {
+                source,
+                target,
+                weight,
+                sign,
+            } => {
+                if !weight.is_finite() || weight < 0. {
+                    return Err(error_at(format!(
+                        "edge weight must be finite and non-negative, got {weight}"
+                    )));
+                }
+                if source == target && weight != 0. {
+                    return Err(error_at("self-edges must carry zero weight".to_string()));
+                }
+
+                match sign {
+                    Sign::Positive => graph.add_positive_edge(source, target, weight),
+                    Sign::Negative => graph.add_negative_edge(source, target, weight),
+                }
+            }
+            Line::PreTrust { node, weight } => {
+                if !weight.is_finite() || weight < 0. {
+                    return Err(error_at(format!(
+                        "pre-trust weight must be finite and non-negative, got {weight}"
+                    )));
+                }
+                pre_trust.insert(node, weight);
+            }
+        }
+    }
+
+    Ok((graph, pre_trust))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_edges_and_pretrust() {
+        let (graph, pre_trust) = parse_graph(
+            "A B 0.6 +\n\
+             B C 0.4 -\n\
+             pretrust A 0.7\n",
+        )
+        .expect("well-formed input parses");
+
+        assert_eq!(
+            graph.get_positive_weight("A".to_string(), "B".to_string()),
+            0.6
+        );
+        assert_eq!(
+            graph.get_negative_weight("B".to_string(), "C".to_string()),
+            0.4
+        );
+        assert_eq!(pre_trust.get("A"), Some(&0.7));
+    }
+
+    #[test]
+    fn blank_lines_and_comments_do_not_shift_line_numbers() {
+        let err = parse_graph(
+            "# a comment\n\
+             \n\
+             A B 0.6 +\n\
+             \n\
+             # another comment\n\
+             this is not a valid line\n",
+        )
+        .expect_err("malformed line must be rejected");
+
+        assert_eq!(err.line, 6);
+    }
+
+    #[test]
+    fn malformed_line_reports_its_own_line_number() {
+        let err = parse_graph("A B 0.6 +\nnot an edge line at all\n")
+            .expect_err("malformed line must be rejected");
+        assert_eq!(err.line, 2);
+        assert!(err.message.contains("malformed edge-list line"));
+    }
+
+    #[test]
+    fn trailing_input_is_rejected() {
+        let err = parse_graph("A B 0.6 + extra\n").expect_err("trailing input must be rejected");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("unexpected trailing input"));
+    }
+
+    #[test]
+    fn negative_edge_weight_is_rejected() {
+        let err = parse_graph("A B -0.1 +\n").expect_err("negative weight must be rejected");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("non-negative"));
+    }
+
+    #[test]
+    fn non_finite_edge_weight_is_rejected() {
+        let err = parse_graph("A B nan +\n").expect_err("NaN weight must be rejected");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("finite"));
+
+        let err = parse_graph("A B inf +\n").expect_err("infinite weight must be rejected");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("finite"));
+    }
+
+    #[test]
+    fn non_finite_pretrust_weight_is_rejected() {
+        let err =
+            parse_graph("pretrust A nan\n").expect_err("NaN pre-trust weight must be rejected");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("finite"));
+    }
+
+    #[test]
+    fn zero_weight_self_edge_is_accepted() {
+        let (graph, _) = parse_graph("A A 0 +\n").expect("zero-weight self-edge is valid");
+        assert_eq!(
+            graph.get_positive_weight("A".to_string(), "A".to_string()),
+            0.
+        );
+    }
+
+    #[test]
+    fn non_zero_weight_self_edge_is_rejected() {
+        let err =
+            parse_graph("A A 0.5 +\n").expect_err("non-zero-weight self-edge must be rejected");
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("self-edges must carry zero weight"));
+    }
+}
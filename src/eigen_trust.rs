@@ -0,0 +1,320 @@
+//! EigenTrust power iteration over the sparse `Graph`/`Node` adjacency from
+//! [`crate::transitive_trust`], replacing the old fixed `NUM_NEIGHBOURS = 5`
+//! dense-matrix kernel so the computation scales to graphs with thousands of
+//! peers without ever materialising an `N x N` matrix.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use crate::transitive_trust::Graph;
+
+/// A node's outgoing local-trust weights, normalised to sum to one.
+type NormalisedEdges = HashMap<String, f32>;
+
+/// Runs the classic EigenTrust update `s[i] += lt[j][i] * s[j]` by iterating
+/// only over edges that exist in the graph.
+pub struct PowerIteration {
+    normalised: HashMap<String, NormalisedEdges>,
+    scores: HashMap<String, f32>,
+}
+
+impl PowerIteration {
+    /// Builds a power iteration over `graph`, column-normalising each node's
+    /// outgoing edges as selected by `weight_of` (e.g. positive or negative
+    /// trust weights). A node whose outgoing weights sum to zero falls back
+    /// to the `pre_trust` vector in its entirety, matching the behaviour of
+    /// the old `normalise` function. The score vector is seeded from
+    /// `pre_trust` as well.
+    pub fn new(
+        graph: &Graph,
+        pre_trust: HashMap<String, f32>,
+        weight_of: impl Fn(&Graph, String, String) -> f32,
+    ) -> Self {
+        Self::validate(graph, &weight_of);
+
+        let mut normalised = HashMap::new();
+        for node in graph.for_each_node() {
+            let mut edges: NormalisedEdges = HashMap::new();
+            let mut sum = 0.0_f32;
+            for neighbour in graph.for_each_neighbour(node.clone()) {
+                let weight = weight_of(graph, node.clone(), neighbour.clone());
+                edges.insert(neighbour, weight);
+                sum += weight;
+            }
+
+            if sum == 0. {
+                normalised.insert(node, pre_trust.clone());
+            } else {
+                for weight in edges.values_mut() {
+                    *weight /= sum;
+                }
+                normalised.insert(node, edges);
+            }
+        }
+
+        let scores = graph
+            .for_each_node()
+            .map(|node| {
+                let score = pre_trust.get(&node).copied().unwrap_or(0.);
+                (node, score)
+            })
+            .collect();
+
+        Self { normalised, scores }
+    }
+
+    /// Overrides the current score vector, e.g. to seed a distrust pass from
+    /// the output of a prior positive run instead of the pre-trust vector.
+    pub fn with_scores(mut self, scores: HashMap<String, f32>) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    /// Applies the `s[i] += lt[j][i] * s[j]` update once.
+    pub fn step(&mut self) {
+        let mut new_scores: HashMap<String, f32> =
+            self.scores.keys().map(|node| (node.clone(), 0.)).collect();
+
+        for (node, edges) in &self.normalised {
+            let s_j = *self.scores.get(node).unwrap_or(&0.);
+            for (target, weight) in edges {
+                *new_scores.entry(target.clone()).or_insert(0.) += weight * s_j;
+            }
+        }
+
+        self.scores = new_scores;
+    }
+
+    /// Runs `iterations` applications of [`Self::step`] and returns the
+    /// resulting score vector.
+    pub fn run(mut self, iterations: usize) -> HashMap<String, f32> {
+        for _ in 0..iterations {
+            self.step();
+        }
+        self.scores
+    }
+
+    /// Iterates until the L1 delta between successive score vectors drops
+    /// below `tolerance`, or `max_iters` is reached, whichever comes first.
+    /// Returns the final scores alongside the number of iterations actually
+    /// used, so callers no longer have to run a fixed `NUM_ITER` regardless
+    /// of whether the vector has already stabilized.
+    pub fn run_until_converged(
+        mut self,
+        tolerance: f32,
+        max_iters: usize,
+    ) -> (HashMap<String, f32>, usize) {
+        for iteration in 1..=max_iters {
+            let previous = self.scores.clone();
+            self.step();
+
+            let delta: f32 = self
+                .scores
+                .iter()
+                .map(|(node, score)| (score - previous.get(node).copied().unwrap_or(0.)).abs())
+                .sum();
+
+            if delta < tolerance {
+                return (self.scores, iteration);
+            }
+        }
+
+        (self.scores, max_iters)
+    }
+
+    /// Like [`Self::run_until_converged`], but also records every
+    /// intermediate score vector, starting with the seed, so a
+    /// [`crate::proof::Prover`] can commit to the full iteration history.
+    pub fn run_until_converged_recording(
+        mut self,
+        tolerance: f32,
+        max_iters: usize,
+    ) -> (HashMap<String, f32>, usize, Vec<HashMap<String, f32>>) {
+        let mut history = vec![self.scores.clone()];
+
+        for iteration in 1..=max_iters {
+            let previous = self.scores.clone();
+            self.step();
+            history.push(self.scores.clone());
+
+            let delta: f32 = self
+                .scores
+                .iter()
+                .map(|(node, score)| (score - previous.get(node).copied().unwrap_or(0.)).abs())
+                .sum();
+
+            if delta < tolerance {
+                return (self.scores, iteration, history);
+            }
+        }
+
+        (self.scores, max_iters, history)
+    }
+
+    /// Mirrors the old `validate_lt`: self-edges must carry zero weight (not
+    /// be absent — a zero-weight self-edge is valid, matching what
+    /// [`crate::edge_list::parse_graph`] accepts) and all weights must be
+    /// non-negative.
+    fn validate(graph: &Graph, weight_of: &impl Fn(&Graph, String, String) -> f32) {
+        for node in graph.for_each_node() {
+            for neighbour in graph.for_each_neighbour(node.clone()) {
+                let weight = weight_of(graph, node.clone(), neighbour.clone());
+                if neighbour == node {
+                    assert_eq!(weight, 0., "self-edges must carry zero weight");
+                }
+                assert!(weight >= 0., "trust weights must be non-negative");
+            }
+        }
+    }
+}
+
+/// A single neighbor's recorded local trust, resulting global trust, and
+/// their product. `local_trust` is only overwritten on the epoch it actually
+/// changes on, tracked by `local_trust_epoch`; `global_trust` is refreshed
+/// on every [`OpinionCache::record`] call regardless.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Opinion {
+    pub local_trust: f32,
+    pub global_trust: f32,
+    pub local_trust_epoch: u64,
+}
+
+impl Opinion {
+    pub fn product(&self) -> f32 {
+        self.local_trust * self.global_trust
+    }
+}
+
+/// Caches per-neighbor [`Opinion`]s across epochs, keyed by `(source,
+/// target)`, so incremental/streaming trust updates can look up the most
+/// recently recorded local and global trust for any edge.
+pub struct OpinionCache {
+    epoch: u64,
+    opinions: HashMap<(String, String), Opinion>,
+}
+
+impl OpinionCache {
+    pub fn new() -> Self {
+        Self {
+            epoch: 0,
+            opinions: HashMap::new(),
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Advances the epoch counter and records an opinion for every edge in
+    /// `graph`, using `scores` as the resulting global trust for the edge's
+    /// target. `global_trust` is refreshed every epoch regardless, so
+    /// `get`/`product` never return a global score from a prior epoch. An
+    /// edge whose local trust weight is unchanged from the prior epoch
+    /// reuses its cached `local_trust` and keeps its `local_trust_epoch`
+    /// instead of bumping it, so callers can tell how long an edge's weight
+    /// has been stable.
+    pub fn record(
+        &mut self,
+        graph: &Graph,
+        scores: &HashMap<String, f32>,
+        weight_of: impl Fn(&Graph, String, String) -> f32,
+    ) -> u64 {
+        self.epoch += 1;
+
+        for node in graph.for_each_node() {
+            for neighbour in graph.for_each_neighbour(node.clone()) {
+                let weight = weight_of(graph, node.clone(), neighbour.clone());
+                let global_trust = scores.get(&neighbour).copied().unwrap_or(0.);
+                let key = (node.clone(), neighbour.clone());
+
+                match self.opinions.get_mut(&key) {
+                    Some(opinion) if opinion.local_trust == weight => {
+                        opinion.global_trust = global_trust;
+                    }
+                    Some(opinion) => {
+                        opinion.local_trust = weight;
+                        opinion.global_trust = global_trust;
+                        opinion.local_trust_epoch = self.epoch;
+                    }
+                    None => {
+                        self.opinions.insert(
+                            key,
+                            Opinion {
+                                local_trust: weight,
+                                global_trust,
+                                local_trust_epoch: self.epoch,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        self.epoch
+    }
+
+    pub fn get(&self, source: &str, target: &str) -> Option<Opinion> {
+        self.opinions
+            .get(&(source.to_string(), target.to_string()))
+            .copied()
+    }
+}
+
+impl Default for OpinionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transitive_trust::Graph;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::new();
+        graph.add_positive_edge("A".to_string(), "B".to_string(), 0.5);
+        graph
+    }
+
+    #[test]
+    fn global_trust_refreshes_even_when_local_trust_is_unchanged() {
+        let graph = graph();
+        let mut cache = OpinionCache::new();
+
+        let mut scores = HashMap::new();
+        scores.insert("B".to_string(), 1.0);
+        cache.record(&graph, &scores, Graph::get_positive_weight);
+
+        scores.insert("B".to_string(), 2.0);
+        cache.record(&graph, &scores, Graph::get_positive_weight);
+
+        let opinion = cache.get("A", "B").expect("opinion recorded");
+        assert_eq!(opinion.global_trust, 2.0);
+        assert_eq!(opinion.product(), 1.0);
+    }
+
+    #[test]
+    fn local_trust_epoch_only_advances_when_the_weight_changes() {
+        let mut graph = graph();
+        let mut cache = OpinionCache::new();
+        let scores = HashMap::new();
+
+        cache.record(&graph, &scores, Graph::get_positive_weight);
+        let first_epoch = cache.get("A", "B").unwrap().local_trust_epoch;
+        assert_eq!(first_epoch, 1);
+
+        cache.record(&graph, &scores, Graph::get_positive_weight);
+        let unchanged_epoch = cache.get("A", "B").unwrap().local_trust_epoch;
+        assert_eq!(
+            unchanged_epoch, first_epoch,
+            "weight didn't change, epoch shouldn't bump"
+        );
+
+        graph.add_positive_edge("A".to_string(), "B".to_string(), 0.9);
+        cache.record(&graph, &scores, Graph::get_positive_weight);
+        let changed_epoch = cache.get("A", "B").unwrap().local_trust_epoch;
+        assert_eq!(changed_epoch, 3);
+    }
+}
@@ -1,151 +1,136 @@
-#![feature(array_zip)]
-
-const NUM_NEIGHBOURS: usize = 5;
-const NUM_ITER: usize = 30;
-
-fn validate_lt(lt: [[f32; NUM_NEIGHBOURS]; NUM_NEIGHBOURS]) {
-    // Compute sum of incoming distrust
-    for i in 0..NUM_NEIGHBOURS {
-        for j in 0..NUM_NEIGHBOURS {
-            // Make sure we are not giving score to ourselves
-            if i == j {
-                assert_eq!(lt[i][j], 0.);
-            }
-            assert!(lt[i][j] >= 0.);
+mod edge_list;
+mod eigen_trust;
+mod proof;
+mod simulation;
+mod transitive_trust;
+mod weighted_shuffle;
+
+use std::collections::HashMap;
+
+use eigen_trust::{OpinionCache, PowerIteration};
+use proof::{Prover, Verifier};
+use transitive_trust::Graph;
+
+const MAX_ITERS: usize = 30;
+const TOLERANCE: f32 = 1e-6;
+
+fn graph_of(edges: &[(&str, &str, f32)], negative: bool) -> Graph {
+    let mut graph = Graph::new();
+    for (source, target, weight) in edges {
+        if negative {
+            graph.add_negative_edge(source.to_string(), target.to_string(), *weight);
+        } else {
+            graph.add_positive_edge(source.to_string(), target.to_string(), *weight);
         }
     }
-}
-
-fn normalise(
-    lt_vec: [f32; NUM_NEIGHBOURS],
-    pre_trust: [f32; NUM_NEIGHBOURS],
-) -> [f32; NUM_NEIGHBOURS] {
-    let sum: f32 = lt_vec.iter().sum();
-    if sum == 0. {
-        return pre_trust;
-    }
-    lt_vec.map(|x| x / sum)
+    graph
 }
 
 fn positive_run(
-    domain: String,
-    mut lt: [[f32; NUM_NEIGHBOURS]; NUM_NEIGHBOURS],
-    pre_trust: [f32; NUM_NEIGHBOURS],
-) -> [f32; NUM_NEIGHBOURS] {
-    println!("");
+    domain: &str,
+    graph: Graph,
+    pre_trust: HashMap<String, f32>,
+    cache: &mut OpinionCache,
+) -> HashMap<String, f32> {
+    println!();
     println!("{} - Trust:", domain);
 
-    validate_lt(lt);
-    for i in 0..NUM_NEIGHBOURS {
-        lt[i] = normalise(lt[i], pre_trust);
-    }
-
-    let mut s = pre_trust.clone();
-    println!("start: [{}]", s.map(|v| format!("{:>9.4}", v)).join(", "));
-    for _ in 0..NUM_ITER {
-        let mut new_s = [0.; 5];
-
-        // Compute sum of incoming weights
-        for i in 0..NUM_NEIGHBOURS {
-            for j in 0..NUM_NEIGHBOURS {
-                new_s[i] += lt[j][i] * s[j];
-            }
-        }
-
-        s = new_s;
+    println!("start: {:?}", pre_trust);
+    let (scores, iterations) = PowerIteration::new(&graph, pre_trust, Graph::get_positive_weight)
+        .run_until_converged(TOLERANCE, MAX_ITERS);
+    println!("end: {:?} (converged in {} iterations)", scores, iterations);
 
-        // println!("[{}]", s.map(|v| format!("{:>9.4}", v)).join(", "));
-    }
-    println!("end: [{}]", s.map(|v| format!("{:>9.4}", v)).join(", "));
+    let epoch = cache.record(&graph, &scores, Graph::get_positive_weight);
+    println!("opinions cached at epoch {}", epoch);
 
-    s
+    scores
 }
 
 fn negative_run(
-    domain: String,
-    mut lt: [[f32; NUM_NEIGHBOURS]; NUM_NEIGHBOURS],
-    s: [f32; NUM_NEIGHBOURS],
-    pre_trust: [f32; NUM_NEIGHBOURS],
-) -> [f32; NUM_NEIGHBOURS] {
-    println!("");
+    domain: &str,
+    graph: Graph,
+    s: HashMap<String, f32>,
+    pre_trust: HashMap<String, f32>,
+) -> HashMap<String, f32> {
+    println!();
     println!("{} - Distrust:", domain);
 
-    validate_lt(lt);
-    for i in 0..NUM_NEIGHBOURS {
-        lt[i] = normalise(lt[i], pre_trust);
-    }
+    let scores = PowerIteration::new(&graph, pre_trust, Graph::get_negative_weight)
+        .with_scores(s)
+        .run(1);
+    println!("end: {:?}", scores);
 
-    let mut new_s = [0.0; NUM_NEIGHBOURS];
-    // Compute sum of incoming weights
-    for i in 0..NUM_NEIGHBOURS {
-        for j in 0..NUM_NEIGHBOURS {
-            new_s[i] += lt[j][i] * s[j];
-        }
-    }
-
-    println!("end: [{}]", new_s.map(|v| format!("{:>9.4}", v)).join(", "));
-    new_s
+    scores
 }
 
 fn main() {
-    let pre_trust: [f32; NUM_NEIGHBOURS] = [0.0, 0.0, 0.0, 0.7, 0.3];
-
-    let lt_sd: [[f32; NUM_NEIGHBOURS]; NUM_NEIGHBOURS] = [
-        [0.0, 0.0, 0.5, 0.0, 0.0], // - Peer 0 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 1 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 2 opinions
-        [5.5, 0.0, 0.0, 0.0, 0.0], // - Peer 3 opinions
-        [0.0, 5.0, 0.0, 0.0, 0.0], // = Peer 4 opinions
-    ];
-
-    let sd_s = positive_run("Software Development".to_string(), lt_sd, pre_trust);
-
-    let ld_sd: [[f32; NUM_NEIGHBOURS]; NUM_NEIGHBOURS] = [
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 0 opinions
-        [0.0, 0.0, 5.0, 0.0, 0.0], // - Peer 1 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 2 opinions
-        [0.0, 0.5, 0.0, 0.0, 0.0], // - Peer 3 opinions
-        [5.0, 0.0, 0.0, 0.0, 0.0], // = Peer 4 opinions
-    ];
-
-    negative_run("Software Development".to_string(), ld_sd, pre_trust, sd_s);
-
-    let lt_ss: [[f32; NUM_NEIGHBOURS]; NUM_NEIGHBOURS] = [
-        [0.0, 0.0, 0.5, 0.0, 0.0], // - Peer 0 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 1 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 2 opinions
-        [0.5, 0.0, 0.0, 0.0, 0.0], // - Peer 3 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // = Peer 4 opinions
-    ];
-
-    let ss_s = positive_run("Software Security".to_string(), lt_ss, pre_trust);
-
-    let ld_ss: [[f32; NUM_NEIGHBOURS]; NUM_NEIGHBOURS] = [
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 0 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 1 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // - Peer 2 opinions
-        [0.0, 0.5, 0.0, 0.0, 0.0], // - Peer 3 opinions
-        [0.0, 0.0, 0.0, 0.0, 0.0], // = Peer 4 opinions
-    ];
-
-    negative_run("Software Security".to_string(), ld_ss, pre_trust, ss_s);
-
-    let snap1_trust: [f32; NUM_NEIGHBOURS] = [25., 0., 0., 25., 0.];
-    let snap1_distrust: [f32; NUM_NEIGHBOURS] = [0., 0., 5., 0., 0.];
-
-    let snap2_trust: [f32; NUM_NEIGHBOURS] = [0., 0., 0., 0., 25.];
-    let snap2_distrust: [f32; NUM_NEIGHBOURS] = [0., 0., 5., 5., 0.];
-
-    let snap1_score: f32 = ss_s
-        .zip(snap1_trust.zip(snap1_distrust))
-        .iter()
-        .fold(0., |acc, (s, (t, d))| 0.);
-
-    let snap2_score: f32 = ss_s
-        .zip(snap2_trust.zip(snap2_distrust))
-        .iter()
-        .fold(0., |acc, (s, (t, d))| 0.);
-
-    println!("snap1 score: {}", snap1_score);
-    println!("snap2 score: {}", snap2_score);
-}
\ No newline at end of file
+    let mut opinions = OpinionCache::new();
+
+    // Loaded from the edge-list format instead of an inline literal, to show
+    // graphs can now come from external datasets without recompiling.
+    let sd_edge_list = "\
+        0 2 0.5 +\n\
+        3 0 5.5 +\n\
+        4 1 5.0 +\n\
+        pretrust 3 0.7\n\
+        pretrust 4 0.3\n";
+    let (sd_graph, sd_pre_trust) = edge_list::parse_graph(sd_edge_list)
+        .expect("Software Development edge list is well-formed");
+
+    // Prove the Software Development trust scores are correct, then verify
+    // the proof independently of the run above.
+    let (_, transcript) = Prover::prove(
+        &sd_graph,
+        sd_pre_trust.clone(),
+        Graph::get_positive_weight,
+        TOLERANCE,
+        MAX_ITERS,
+    );
+    let verified = Verifier::verify(
+        &sd_graph,
+        sd_pre_trust.clone(),
+        Graph::get_positive_weight,
+        TOLERANCE,
+        MAX_ITERS,
+        &transcript,
+    );
+    println!(
+        "Software Development - proof commitment: {:x?}",
+        transcript.commitment()
+    );
+    println!(
+        "Software Development - independently verified: {}",
+        verified
+    );
+
+    let sd_s = positive_run(
+        "Software Development",
+        sd_graph,
+        sd_pre_trust.clone(),
+        &mut opinions,
+    );
+
+    let ld_sd: [(&str, &str, f32); 3] = [("1", "2", 5.0), ("3", "1", 0.5), ("4", "0", 5.0)];
+    negative_run(
+        "Software Development",
+        graph_of(&ld_sd, true),
+        sd_s,
+        sd_pre_trust.clone(),
+    );
+
+    let lt_ss: [(&str, &str, f32); 2] = [("0", "2", 0.5), ("3", "0", 0.5)];
+    let ss_s = positive_run(
+        "Software Security",
+        graph_of(&lt_ss, false),
+        sd_pre_trust.clone(),
+        &mut opinions,
+    );
+
+    let ld_ss: [(&str, &str, f32); 1] = [("3", "1", 0.5)];
+    negative_run(
+        "Software Security",
+        graph_of(&ld_ss, true),
+        ss_s.clone(),
+        sd_pre_trust,
+    );
+}
@@ -0,0 +1,138 @@
+//! Verifiable-computation support for the EigenTrust kernel: a
+//! `Prover`/`Verifier` pair so an independent party can confirm a set of
+//! scores was produced by the deterministic `PowerIteration` kernel, without
+//! trusting whoever ran it. The prover runs the kernel while recording every
+//! intermediate score vector, then commits to the input graph, the
+//! pre-trust vector, and that full iteration history; the verifier re-runs
+//! the same kernel over the same inputs and checks the commitments match.
+
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha256};
+
+use crate::eigen_trust::PowerIteration;
+use crate::transitive_trust::Graph;
+
+/// A transcript committing to one power-iteration run: a commitment to the
+/// input graph and pre-trust vector, every intermediate score vector, and
+/// the final scores.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transcript {
+    input_commitment: [u8; 32],
+    iterations: Vec<Vec<(String, f32)>>,
+    final_scores: Vec<(String, f32)>,
+}
+
+impl Transcript {
+    /// Serializes the transcript to a stable byte format: score vectors are
+    /// sorted by node first, so two transcripts of the same run hash
+    /// identically regardless of `HashMap` iteration order.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.input_commitment.to_vec();
+        for iteration in &self.iterations {
+            encode_entries(iteration, &mut bytes);
+            bytes.push(b'\n');
+        }
+        encode_entries(&self.final_scores, &mut bytes);
+        bytes
+    }
+
+    /// The commitment an independent verifier checks its own transcript
+    /// against.
+    pub fn commitment(&self) -> [u8; 32] {
+        Sha256::digest(self.to_bytes()).into()
+    }
+}
+
+fn encode_entries(entries: &[(String, f32)], out: &mut Vec<u8>) {
+    for (node, score) in entries {
+        out.extend_from_slice(node.as_bytes());
+        out.push(b'=');
+        out.extend_from_slice(&score.to_le_bytes());
+        out.push(b';');
+    }
+}
+
+fn sorted_entries(scores: &HashMap<String, f32>) -> Vec<(String, f32)> {
+    let mut entries: Vec<(String, f32)> = scores
+        .iter()
+        .map(|(node, score)| (node.clone(), *score))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// Commits to the graph's adjacency and weights (as selected by `weight_of`)
+/// plus the pre-trust vector, so a transcript can't be replayed against a
+/// different input and pass verification.
+fn commit_inputs(
+    graph: &Graph,
+    pre_trust: &HashMap<String, f32>,
+    weight_of: &impl Fn(&Graph, String, String) -> f32,
+) -> [u8; 32] {
+    let mut bytes = Vec::new();
+
+    let mut nodes: Vec<String> = graph.for_each_node().collect();
+    nodes.sort();
+    for node in &nodes {
+        let mut neighbours: Vec<String> = graph.for_each_neighbour(node.clone()).collect();
+        neighbours.sort();
+        for neighbour in neighbours {
+            let weight = weight_of(graph, node.clone(), neighbour.clone());
+            bytes.extend_from_slice(node.as_bytes());
+            bytes.push(b'-');
+            bytes.extend_from_slice(neighbour.as_bytes());
+            bytes.push(b'=');
+            bytes.extend_from_slice(&weight.to_le_bytes());
+            bytes.push(b';');
+        }
+    }
+
+    encode_entries(&sorted_entries(pre_trust), &mut bytes);
+    Sha256::digest(bytes).into()
+}
+
+/// Produces a [`Transcript`] alongside the computed scores.
+pub struct Prover;
+
+impl Prover {
+    pub fn prove(
+        graph: &Graph,
+        pre_trust: HashMap<String, f32>,
+        weight_of: impl Fn(&Graph, String, String) -> f32,
+        tolerance: f32,
+        max_iters: usize,
+    ) -> (HashMap<String, f32>, Transcript) {
+        let input_commitment = commit_inputs(graph, &pre_trust, &weight_of);
+
+        let (scores, _iterations, history) = PowerIteration::new(graph, pre_trust, &weight_of)
+            .run_until_converged_recording(tolerance, max_iters);
+
+        let transcript = Transcript {
+            input_commitment,
+            iterations: history.iter().map(sorted_entries).collect(),
+            final_scores: sorted_entries(&scores),
+        };
+
+        (scores, transcript)
+    }
+}
+
+/// Re-runs the deterministic kernel over the claimed inputs and confirms the
+/// result matches a [`Transcript`]'s commitment.
+pub struct Verifier;
+
+impl Verifier {
+    pub fn verify(
+        graph: &Graph,
+        pre_trust: HashMap<String, f32>,
+        weight_of: impl Fn(&Graph, String, String) -> f32,
+        tolerance: f32,
+        max_iters: usize,
+        transcript: &Transcript,
+    ) -> bool {
+        let (_scores, recomputed) =
+            Prover::prove(graph, pre_trust, weight_of, tolerance, max_iters);
+        recomputed.commitment() == transcript.commitment()
+    }
+}
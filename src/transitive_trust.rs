@@ -1,11 +1,16 @@
+#![allow(dead_code)]
+
+use ordered_float::OrderedFloat;
 use priority_queue::PriorityQueue;
 use std::{
     collections::{HashMap, HashSet},
     vec::IntoIter,
 };
 
+use crate::weighted_shuffle::WeightedShuffle;
+
 #[derive(Debug, Clone)]
-struct Result {
+pub(crate) struct Result {
     node: String,
     p_score: f32,
     n_score: f32,
@@ -64,7 +69,7 @@ impl Node {
 }
 
 #[derive(Debug, Clone)]
-struct Graph {
+pub struct Graph {
     nodes: HashMap<String, Node>,
 }
 
@@ -120,61 +125,96 @@ impl Graph {
     }
 }
 
+/// Pops every candidate currently tied for the top priority in `pq` and
+/// returns them in a bias-free, weight-proportional order (weighted by each
+/// candidate's `p_score`, i.e. its stake) rather than whatever arbitrary
+/// order the underlying heap happens to hold them in.
+fn pop_tied_group(
+    pq: &mut PriorityQueue<String, OrderedFloat<f32>>,
+    p_scores: &HashMap<String, f32>,
+    shuffle: &mut WeightedShuffle,
+) -> Vec<String> {
+    let Some((_, &top_priority)) = pq.peek() else {
+        return Vec::new();
+    };
+
+    let tied: Vec<String> = pq
+        .iter()
+        .filter(|(_, &priority)| priority == top_priority)
+        .map(|(node, _)| node.clone())
+        .collect();
+
+    let weighted: Vec<(String, f32)> = tied
+        .iter()
+        .map(|node| (node.clone(), p_scores.get(node).copied().unwrap_or(0.)))
+        .collect();
+
+    for node in &tied {
+        pq.remove(node);
+    }
+
+    shuffle.shuffle(&weighted)
+}
+
 pub fn compute_scores(graph: Graph, source: String) -> Vec<Result> {
     let mut p_scores = HashMap::<String, f32>::new();
     let mut n_scores = HashMap::<String, f32>::new();
     let mut inspected = HashSet::<String>::new();
-    let mut pq = PriorityQueue::<String, u32>::new();
+    let mut pq = PriorityQueue::<String, OrderedFloat<f32>>::new();
+    let mut shuffle = WeightedShuffle::new(&source);
 
     for node in graph.for_each_node() {
         let p_score = if node == source { 1. } else { 0. };
         p_scores.insert(node.clone(), p_score);
         n_scores.insert(node.clone(), 0.);
-        pq.push(node, (p_score * 10.0) as u32);
+        pq.push(node, OrderedFloat(p_score));
     }
 
     while !pq.is_empty() {
-        let (node_key, _) = pq.pop().unwrap();
-        if inspected.contains(&node_key) {
-            continue;
-        }
-        inspected.insert(node_key.clone());
-
-        let node_score =
-            (p_scores.get(&node_key).unwrap() - n_scores.get(&node_key).unwrap()).max(0.);
-
-        for neighbor_key in graph.for_each_neighbour(node_key.clone()) {
-            let neighbor_score =
-                p_scores.get(&neighbor_key).unwrap() - n_scores.get(&neighbor_key).unwrap();
-
-            if inspected.contains(&neighbor_key) || neighbor_score > node_score {
+        for node_key in pop_tied_group(&mut pq, &p_scores, &mut shuffle) {
+            if inspected.contains(&node_key) {
                 continue;
             }
-
-            let positive_weight = graph.get_positive_weight(node_key.clone(), neighbor_key.clone());
-            let negative_weight = graph.get_negative_weight(node_key.clone(), neighbor_key.clone());
-
-            let neighbour_p_score = p_scores.get(&neighbor_key).unwrap();
-            let neighbour_n_score = n_scores.get(&neighbor_key).unwrap();
-
-            if node_score > *neighbour_p_score {
-                let new_neighbour_p_score = neighbour_p_score
-                    + (node_score - neighbour_p_score) * f32::from(positive_weight);
-                p_scores.insert(neighbor_key.clone(), new_neighbour_p_score);
-            };
-
-            if node_score > *neighbour_n_score {
-                let new_neighbour_n_score = neighbour_n_score
-                    + (node_score - neighbour_n_score) * f32::from(negative_weight);
-                n_scores.insert(neighbor_key.clone(), new_neighbour_n_score);
-            };
-
-            let neighbour_p_score = p_scores.get(&neighbor_key).unwrap();
-            let neighbour_n_score = n_scores.get(&neighbor_key).unwrap();
-            pq.push(
-                neighbor_key,
-                ((neighbour_p_score - neighbour_n_score) * 10.0) as u32,
-            );
+            inspected.insert(node_key.clone());
+
+            let node_score =
+                (p_scores.get(&node_key).unwrap() - n_scores.get(&node_key).unwrap()).max(0.);
+
+            for neighbor_key in graph.for_each_neighbour(node_key.clone()) {
+                let neighbor_score =
+                    p_scores.get(&neighbor_key).unwrap() - n_scores.get(&neighbor_key).unwrap();
+
+                if inspected.contains(&neighbor_key) || neighbor_score > node_score {
+                    continue;
+                }
+
+                let positive_weight =
+                    graph.get_positive_weight(node_key.clone(), neighbor_key.clone());
+                let negative_weight =
+                    graph.get_negative_weight(node_key.clone(), neighbor_key.clone());
+
+                let neighbour_p_score = p_scores.get(&neighbor_key).unwrap();
+                let neighbour_n_score = n_scores.get(&neighbor_key).unwrap();
+
+                if node_score > *neighbour_p_score {
+                    let new_neighbour_p_score =
+                        neighbour_p_score + (node_score - neighbour_p_score) * positive_weight;
+                    p_scores.insert(neighbor_key.clone(), new_neighbour_p_score);
+                };
+
+                if node_score > *neighbour_n_score {
+                    let new_neighbour_n_score =
+                        neighbour_n_score + (node_score - neighbour_n_score) * negative_weight;
+                    n_scores.insert(neighbor_key.clone(), new_neighbour_n_score);
+                };
+
+                let neighbour_p_score = p_scores.get(&neighbor_key).unwrap();
+                let neighbour_n_score = n_scores.get(&neighbor_key).unwrap();
+                pq.push(
+                    neighbor_key,
+                    OrderedFloat(neighbour_p_score - neighbour_n_score),
+                );
+            }
         }
     }
 
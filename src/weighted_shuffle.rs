@@ -0,0 +1,271 @@
+//! A deterministic, weight-proportional shuffle used to break exact score
+//! ties in [`crate::transitive_trust::compute_scores`] without bias: given a
+//! set of tied candidates, each is drawn with probability proportional to
+//! its weight, without replacement, seeded so that replaying a run from the
+//! same source node reproduces the same order.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Binary-indexed (Fenwick) tree over candidate weights, giving O(log k)
+/// updates and O(log k) prefix-sum lookups.
+struct FenwickTree {
+    tree: Vec<f32>,
+    len: usize,
+}
+
+impl FenwickTree {
+    fn new(weights: &[f32]) -> Self {
+        let len = weights.len();
+        let mut tree = Self {
+            tree: vec![0.; len + 1],
+            len,
+        };
+        for (index, &weight) in weights.iter().enumerate() {
+            tree.add(index, weight);
+        }
+        tree
+    }
+
+    fn add(&mut self, index: usize, delta: f32) {
+        let mut i = index + 1;
+        while i <= self.len {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn prefix_sum(&self, index: usize) -> f32 {
+        let mut i = index + 1;
+        let mut sum = 0.;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+
+    fn total(&self) -> f32 {
+        if self.len == 0 {
+            0.
+        } else {
+            self.prefix_sum(self.len - 1)
+        }
+    }
+
+    /// Finds the smallest index whose cumulative weight exceeds `target`.
+    fn find(&self, target: f32) -> usize {
+        let mut index = 0;
+        let mut remaining = target;
+        let mut step = self.len.next_power_of_two();
+        while step > 0 {
+            let next = index + step;
+            if next <= self.len && self.tree[next] <= remaining {
+                index = next;
+                remaining -= self.tree[next];
+            }
+            step >>= 1;
+        }
+        index
+    }
+}
+
+/// A minimal xorshift64 PRNG. Not cryptographically secure, only
+/// deterministic. Shared with [`crate::simulation`] so adversarial network
+/// generation reproduces the same topology for a given seed.
+pub(crate) struct Rng(u64);
+
+impl Rng {
+    pub(crate) fn seeded(seed: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        let state = hasher.finish();
+        Self(if state == 0 {
+            0x9e3779b97f4a7c15
+        } else {
+            state
+        })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Uniform draw in `[0, upper)`, up to f32 rounding: for some `upper` the
+    /// largest fraction times `upper` rounds to `upper` itself.
+    pub(crate) fn next_f32(&mut self, upper: f32) -> f32 {
+        let fraction = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        fraction * upper
+    }
+}
+
+/// Draws bias-free, weight-proportional orderings of tied candidates, seeded
+/// once from the run's source node so the same run reproduces the same
+/// order.
+pub struct WeightedShuffle {
+    rng: Rng,
+}
+
+impl WeightedShuffle {
+    pub fn new(seed: &str) -> Self {
+        Self {
+            rng: Rng::seeded(seed),
+        }
+    }
+
+    /// Orders `candidates` by repeatedly sampling a uniform value in
+    /// `[0, total_weight)`, binary-searching the Fenwick tree's prefix sums
+    /// for the first index whose cumulative weight exceeds it, emitting
+    /// that candidate, then zeroing its weight and repeating. Candidates
+    /// with non-positive weight are appended afterwards in their original
+    /// relative order.
+    ///
+    /// Repeatedly subtracting a drawn candidate's weight can leave float
+    /// residue in the tree, so `total()` may stay slightly positive, and
+    /// `find` may land back on an index that's already been drawn (or on a
+    /// zero-weight one); in that case we fall back to the lowest-indexed
+    /// undrawn candidate with positive weight instead of emitting a
+    /// duplicate.
+    pub fn shuffle(&mut self, candidates: &[(String, f32)]) -> Vec<String> {
+        let weights: Vec<f32> = candidates
+            .iter()
+            .map(|(_, weight)| weight.max(0.))
+            .collect();
+        let mut tree = FenwickTree::new(&weights);
+        let mut drawn = vec![false; candidates.len()];
+        let mut order = Vec::with_capacity(candidates.len());
+
+        for _ in 0..candidates.len() {
+            let total = tree.total();
+            if total <= 0. {
+                break;
+            }
+
+            let target = self.rng.next_f32(total);
+            let index = tree.find(target);
+            let index = if index < candidates.len() && !drawn[index] && weights[index] > 0. {
+                index
+            } else {
+                match (0..candidates.len()).find(|&i| !drawn[i] && weights[i] > 0.) {
+                    Some(i) => i,
+                    None => break,
+                }
+            };
+
+            order.push(candidates[index].0.clone());
+            drawn[index] = true;
+            tree.add(index, -weights[index]);
+        }
+
+        for (index, (node, _)) in candidates.iter().enumerate() {
+            if !drawn[index] {
+                order.push(node.clone());
+            }
+        }
+
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(weights: &[(&str, f32)]) -> Vec<(String, f32)> {
+        weights
+            .iter()
+            .map(|(node, weight)| (node.to_string(), *weight))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_reproduces_same_order() {
+        let candidates = candidates(&[("A", 1.), ("B", 2.), ("C", 3.), ("D", 4.)]);
+
+        let first = WeightedShuffle::new("source-node").shuffle(&candidates);
+        let second = WeightedShuffle::new("source-node").shuffle(&candidates);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn different_seeds_can_reorder() {
+        let candidates = candidates(&[("A", 1.), ("B", 2.), ("C", 3.), ("D", 4.)]);
+
+        let orders: std::collections::HashSet<Vec<String>> = (0..10)
+            .map(|seed| WeightedShuffle::new(&format!("seed-{seed}")).shuffle(&candidates))
+            .collect();
+
+        assert!(
+            orders.len() > 1,
+            "expected different seeds to produce more than one ordering"
+        );
+    }
+
+    #[test]
+    fn zero_weight_candidates_are_never_drawn_before_positive_ones() {
+        let candidates = candidates(&[("zero-1", 0.), ("positive", 1.), ("zero-2", 0.)]);
+        let order = WeightedShuffle::new("zero-weight-seed").shuffle(&candidates);
+
+        assert_eq!(order.last().unwrap(), "zero-2");
+        assert!(
+            order.iter().position(|node| node == "positive").unwrap()
+                < order.iter().position(|node| node == "zero-2").unwrap()
+        );
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation_of_its_input() {
+        let candidates = candidates(&[("A", 1.), ("B", 0.), ("C", 5.), ("D", 2.)]);
+        let mut order = WeightedShuffle::new("permutation-seed").shuffle(&candidates);
+        order.sort();
+
+        let mut expected: Vec<String> = candidates.iter().map(|(node, _)| node.clone()).collect();
+        expected.sort();
+
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn large_fractional_weight_sets_stay_a_permutation() {
+        // Enough candidates with non-round weights that repeated float
+        // subtraction in the Fenwick tree accumulates residue, to guard
+        // against `find` landing back on an already-drawn index.
+        let candidates: Vec<(String, f32)> = (0..200)
+            .map(|i| (i.to_string(), 0.1 + (i as f32) * 0.037))
+            .collect();
+
+        for seed in 0..20 {
+            let mut order = WeightedShuffle::new(&format!("residue-{seed}")).shuffle(&candidates);
+            assert_eq!(
+                order.len(),
+                candidates.len(),
+                "no candidate should be drawn twice or dropped"
+            );
+
+            order.sort();
+            let mut expected: Vec<String> =
+                candidates.iter().map(|(node, _)| node.clone()).collect();
+            expected.sort();
+            assert_eq!(order, expected);
+        }
+    }
+
+    #[test]
+    fn heavier_candidate_wins_first_draw_more_often() {
+        let candidates = candidates(&[("light", 1.), ("heavy", 99.)]);
+
+        let heavy_wins = (0..50)
+            .filter(|seed| {
+                let order =
+                    WeightedShuffle::new(&format!("proportional-{seed}")).shuffle(&candidates);
+                order[0] == "heavy"
+            })
+            .count();
+
+        assert!(heavy_wins > 25, "expected the overwhelmingly heavier candidate to usually be drawn first, got {heavy_wins}/50");
+    }
+}